@@ -0,0 +1,227 @@
+//! An `async`-friendly alternative to [`AppWorldWrapper`](crate::AppWorldWrapper).
+//! See [`AsyncAppWorldWrapper`].
+
+use crate::AppWorld;
+use std::collections::HashSet;
+use std::future::Future;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{RwLock, RwLockReadGuard};
+use tokio::task::{self, Id};
+
+/// An `async` alternative to [`AppWorldWrapper`](crate::AppWorldWrapper).
+///
+/// Many frontend apps (wasm, tokio-backed desktop) drive state updates from async tasks, where
+/// blocking the executor thread on a lock acquisition is undesirable. `AsyncAppWorldWrapper`
+/// wraps your [`AppWorld`] in a [`tokio::sync::RwLock`] so that [`read`](Self::read) and
+/// [`msg`](Self::msg) can be `.await`ed instead of blocking.
+///
+/// Because an async task (unlike a thread) can move between executor threads, and can hold a
+/// read guard across an `.await` point, the double-read deadlock guard described on
+/// [`AppWorldWrapper::read`](crate::AppWorldWrapper::read) is tracked per-task rather than
+/// per-thread here. Calling [`read`](Self::read) outside of a tokio task is allowed but isn't
+/// tracked, since there's no task to scope the guard to.
+///
+/// The set of tasks currently holding a read guard is scoped to this particular wrapper (and its
+/// clones), not shared globally, so holding a read guard on one `AsyncAppWorldWrapper` never
+/// affects reads on an unrelated one.
+///
+/// # Cloning
+///
+/// Cloning an `AsyncAppWorldWrapper` is a very cheap operation.
+///
+/// All clones hold pointers to the same inner state.
+pub struct AsyncAppWorldWrapper<W: AppWorld> {
+    world: Arc<RwLock<W>>,
+    has_read: Arc<Mutex<HashSet<Id>>>,
+}
+
+impl<W: AppWorld + 'static> AsyncAppWorldWrapper<W> {
+    /// Create a new AsyncAppWorldWrapper.
+    pub fn new(world: W) -> Self {
+        let world = Arc::new(RwLock::new(world));
+        Self {
+            world,
+            has_read: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Acquire write access to the AppWorld then send a message, without blocking the executor
+    /// thread while waiting for the lock.
+    pub async fn msg(&self, msg: W::Message) {
+        self.world.write().await.msg(msg)
+    }
+
+    /// Acquire write access to the AppWorld and run an async message handler against it, without
+    /// blocking the executor thread while waiting for the lock or while the handler awaits.
+    ///
+    /// This is useful when applying a message requires first awaiting some I/O (a network
+    /// request, a file read, ...) before the resulting state change should be committed.
+    pub async fn msg_with<F, Fut>(&self, handler: F)
+    where
+        F: FnOnce(&mut W) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let mut guard = self.world.write().await;
+        handler(&mut guard).await;
+    }
+
+    /// Acquire read access to AppWorld, without blocking the executor thread while waiting for
+    /// the lock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current task is already holding a read guard, for the same reason that
+    /// [`AppWorldWrapper::read`](crate::AppWorldWrapper::read) panics on a same-thread double
+    /// read.
+    pub async fn read(&self) -> AsyncWorldReadGuard<'_, W> {
+        let task_id = task::try_id();
+
+        if let Some(task_id) = task_id {
+            if self.has_read.lock().unwrap().contains(&task_id) {
+                panic!("Task already holds read guard")
+            }
+        }
+
+        let guard = self.world.read().await;
+
+        // Only recorded once the guard is actually in hand. If this call is cancelled (e.g. the
+        // future is dropped while still awaiting the lock above), `AsyncWorldReadGuard::drop`
+        // never runs, so inserting any earlier would leak `task_id` in `has_read` and make every
+        // later read from this task spuriously panic.
+        if let Some(task_id) = task_id {
+            self.has_read.lock().unwrap().insert(task_id);
+        }
+
+        AsyncWorldReadGuard {
+            guard,
+            task_id,
+            has_read: self.has_read.clone(),
+        }
+    }
+}
+
+impl<W: AppWorld> Clone for AsyncAppWorldWrapper<W> {
+    fn clone(&self) -> Self {
+        AsyncAppWorldWrapper {
+            world: self.world.clone(),
+            has_read: self.has_read.clone(),
+        }
+    }
+}
+
+/// Holds a read guard on a World acquired through an [`AsyncAppWorldWrapper`].
+pub struct AsyncWorldReadGuard<'a, W> {
+    guard: RwLockReadGuard<'a, W>,
+    task_id: Option<Id>,
+    has_read: Arc<Mutex<HashSet<Id>>>,
+}
+impl<'a, W> Deref for AsyncWorldReadGuard<'a, W> {
+    type Target = RwLockReadGuard<'a, W>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+impl<'a, W> Drop for AsyncWorldReadGuard<'a, W> {
+    fn drop(&mut self) {
+        if let Some(task_id) = self.task_id {
+            self.has_read.lock().unwrap().remove(&task_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default, Clone)]
+    struct Counter(i32);
+    impl AppWorld for Counter {
+        type Message = i32;
+        fn msg(&mut self, message: i32) {
+            self.0 += message;
+        }
+    }
+
+    #[tokio::test]
+    async fn read_observes_a_prior_msg() {
+        let world = AsyncAppWorldWrapper::new(Counter::default());
+
+        world.msg(5).await;
+
+        assert_eq!(world.read().await.0, 5);
+    }
+
+    #[tokio::test]
+    async fn same_task_double_read_panics() {
+        let world = AsyncAppWorldWrapper::new(Counter::default());
+
+        // The double-read guard is tracked per `tokio::task::Id`, and the `#[tokio::test]` body
+        // itself isn't a spawned task (`tokio::task::try_id()` is `None` there, matching the
+        // "outside of a tokio task" case documented on `read`), so the double read has to happen
+        // inside a real spawned task to observe the panic.
+        let result = tokio::spawn(async move {
+            let _first = world.read().await;
+            let _second = world.read().await;
+        })
+        .await;
+
+        assert!(result.is_err(), "expected the spawned task to panic");
+    }
+
+    #[tokio::test]
+    async fn dropping_a_read_guard_lets_the_task_read_again() {
+        let world = AsyncAppWorldWrapper::new(Counter::default());
+
+        // `has_read` is tracked per `tokio::task::Id`, so the reads need to happen inside a real
+        // spawned task (the `#[tokio::test]` body itself has no task id) to actually exercise it.
+        tokio::spawn(async move {
+            {
+                let _guard = world.read().await;
+            }
+
+            let _guard = world.read().await;
+        })
+        .await
+        .unwrap();
+    }
+
+    /// Regression test: a `read()` call that's cancelled while still waiting on the lock must not
+    /// leak its task id into `has_read`, or a later read from the very same still-running task
+    /// would spuriously panic as a double-read.
+    #[tokio::test]
+    async fn a_cancelled_read_does_not_leak_the_task_id() {
+        let world = AsyncAppWorldWrapper::new(Counter::default());
+        let write_guard = world.world.write().await;
+
+        let task = {
+            let world = world.clone();
+            tokio::spawn(async move {
+                // Poll `read()` once and drop it without ever awaiting it to completion, which is
+                // what happens to the losing branch of a `select!` or a future dropped mid-poll.
+                // The manual poll still runs inside this task's context, so `task::try_id()`
+                // inside `read()` sees this task's real id (unlike a bare `#[tokio::test]` body,
+                // which isn't itself a spawned task).
+                {
+                    let mut read = std::pin::pin!(world.read());
+                    let waker = std::task::Waker::noop();
+                    let mut cx = std::task::Context::from_waker(waker);
+                    assert!(
+                        read.as_mut().poll(&mut cx).is_pending(),
+                        "expected the read to still be waiting on the write lock"
+                    );
+                }
+
+                // Without the fix, the cancelled read above would already have inserted this
+                // task's id into `has_read`, making this read spuriously panic as a double-read.
+                let _guard = world.read().await;
+            })
+        };
+
+        tokio::task::yield_now().await;
+        drop(write_guard);
+
+        task.await.unwrap();
+    }
+}
@@ -0,0 +1,45 @@
+//! Error types returned by the fallible [`AppWorldWrapper`](crate::AppWorldWrapper) methods.
+
+use std::fmt;
+
+/// Error returned by [`AppWorldWrapper::try_msg`](crate::AppWorldWrapper::try_msg) and
+/// [`AppWorldWrapper::try_read`](crate::AppWorldWrapper::try_read).
+#[derive(Debug)]
+pub enum WorldError {
+    /// The lock couldn't be acquired without blocking (another reader/writer currently holds
+    /// it).
+    WouldBlock,
+    /// A previous `msg` handler panicked while holding the write lock, poisoning it. Call
+    /// [`AppWorldWrapper::recover`](crate::AppWorldWrapper::recover) to clear the poisoning.
+    Poisoned,
+    /// The `msg` handler passed to `try_msg` panicked. Unlike [`WorldError::Poisoned`], the lock
+    /// was *not* poisoned by this, since `try_msg` catches the unwind, but the world may be left
+    /// in whatever partially-updated state the handler had reached before panicking.
+    HandlerPanicked,
+    /// The current thread is already holding a read guard. Unlike
+    /// [`AppWorldWrapper::read`](crate::AppWorldWrapper::read), `try_read` reports this instead
+    /// of panicking, so a latency-sensitive caller (e.g. a render loop) can gracefully skip work
+    /// for this frame instead of aborting.
+    AlreadyReading,
+}
+
+impl fmt::Display for WorldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorldError::WouldBlock => {
+                write!(f, "the world's lock is currently held and could not be acquired without blocking")
+            }
+            WorldError::Poisoned => {
+                write!(f, "the world's lock is poisoned; call AppWorldWrapper::recover to reset it")
+            }
+            WorldError::HandlerPanicked => {
+                write!(f, "the message handler panicked; the world may be left in a partially-updated state")
+            }
+            WorldError::AlreadyReading => {
+                write!(f, "the current thread already holds a read guard")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WorldError {}
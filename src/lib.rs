@@ -72,13 +72,48 @@
 //! If you're writing a game or simulation you're likely better off reaching for an
 //! entity-component-system library. Otherwise, you should be in good hands here.
 //! which could be an issue for a high-performing game or simulation.
+//!
+//! # Scaling Reads
+//!
+//! [`AppWorldWrapper`] is a single `Arc<RwLock<W>>`, so every reader and writer contends on one
+//! lock. If your app is read-heavy and `W`/`W::Message` are cheap to [`Clone`], you can opt into
+//! [`ReplicatedAppWorld`] instead, which replicates `W` across several independent locks so
+//! concurrent readers on different replicas never block each other.
+//!
+//! # Async
+//!
+//! If your app drives state updates from `async` tasks, enable the `async` feature and use
+//! [`AsyncAppWorldWrapper`] instead of [`AppWorldWrapper`]. It has the same single-writer
+//! `msg`/`read` API, but both are `.await`-able instead of blocking the executor thread.
+//!
+//! # wasm32
+//!
+//! On `wasm32-unknown-unknown`, [`AppWorldWrapper`] automatically swaps its `Arc<RwLock<_>>` for
+//! an `Rc<RefCell<_>>`, since there's only one thread and the atomics/locking would be pure
+//! overhead. The public API is unchanged; a double-read still panics with the same
+//! "Thread already holds read guard" message as on native targets.
 
 #![deny(missing_docs)]
 
+#[cfg(not(target_arch = "wasm32"))]
 use std::cell::RefCell;
 use std::ops::Deref;
-use std::sync::{Arc, RwLock, RwLockReadGuard};
-use std::thread::LocalKey;
+
+mod lock;
+use lock::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[cfg(not(target_arch = "wasm32"))]
+use lock::thread_local;
+
+mod error;
+pub use error::WorldError;
+
+mod replicated;
+pub use replicated::{ReplicatedAppWorld, ReplicatedReadGuard};
+
+#[cfg(feature = "async")]
+mod async_world;
+#[cfg(feature = "async")]
+pub use async_world::{AsyncAppWorldWrapper, AsyncWorldReadGuard};
 
 /// Holds application state and resources.
 /// See the [crate level documentation](crate) for more details.
@@ -122,13 +157,105 @@ impl<W: AppWorld + 'static> AppWorldWrapper<W> {
     pub fn msg(&self, msg: W::Message) {
         self.world.write().unwrap().msg(msg)
     }
+
+    /// Like [`msg`](Self::msg), but instead of blocking and panicking, returns a [`WorldError`]
+    /// if the lock can't be acquired without blocking or is poisoned.
+    ///
+    /// If the message handler itself panics, the panic is caught so that it doesn't poison the
+    /// lock (unlike a panic inside [`msg`](Self::msg)), and `Err(WorldError::HandlerPanicked)` is
+    /// returned. The world may be left in whatever partially-updated state the handler had
+    /// reached before panicking.
+    ///
+    /// Note for render-loop callers that only need a `bool`: this returns the richer
+    /// `Result<(), WorldError>` rather than a plain success/fail flag, so that `WouldBlock`,
+    /// `Poisoned`, and `HandlerPanicked` stay distinguishable at the call site. Match on the
+    /// error (or use [`Result::is_ok`]) if you just want a yes/no.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn try_msg(&self, msg: W::Message) -> Result<(), WorldError> {
+        let mut guard = lock::try_write(&self.world)?;
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| guard.msg(msg)))
+            .map_err(|_| WorldError::HandlerPanicked)
+    }
+
+    /// Clear poisoning left behind by a panicked [`msg`](Self::msg) call, and hand back a write
+    /// guard so the app can reset state before resuming normal use.
+    ///
+    /// # Panics
+    /// Panics if the lock isn't actually poisoned.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn recover(&self) -> RwLockWriteGuard<'_, W> {
+        match self.world.write() {
+            Ok(_) => panic!("AppWorldWrapper::recover called but the lock wasn't poisoned"),
+            Err(poisoned) => {
+                self.world.clear_poison();
+                poisoned.into_inner()
+            }
+        }
+    }
 }
 
-impl<W: AppWorld + 'static> AppWorldWrapper<W> {
-    thread_local!(
-        static HAS_READ: RefCell<bool> = RefCell::new(false);
-    );
+// There's only one thread on wasm32, so there's no deadlock for this to prevent there, but a
+// double-read is still rejected there (two shared `RefCell` borrows wouldn't panic on their
+// own). A plain static flag stands in for the `thread_local!` on that target.
+
+#[cfg(not(target_arch = "wasm32"))]
+thread_local!(
+    static HAS_READ: RefCell<bool> = RefCell::new(false);
+);
+#[cfg(target_arch = "wasm32")]
+static HAS_READ: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Panics if the current thread already holds a read guard, otherwise marks it as holding one.
+#[cfg(not(target_arch = "wasm32"))]
+fn mark_reading() {
+    HAS_READ.with(|has_read| {
+        let mut has_read = has_read.borrow_mut();
+
+        if *has_read {
+            panic!("Thread already holds read guard")
+        }
+
+        *has_read = true
+    });
+}
+#[cfg(target_arch = "wasm32")]
+fn mark_reading() {
+    if HAS_READ.swap(true, std::sync::atomic::Ordering::Relaxed) {
+        panic!("Thread already holds read guard")
+    }
+}
+
+/// Returns `true` (without marking the thread as reading) if the current thread already holds a
+/// read guard, otherwise marks it as holding one and returns `false`.
+#[cfg(not(target_arch = "wasm32"))]
+fn try_mark_reading() -> bool {
+    HAS_READ.with(|has_read| {
+        let mut has_read = has_read.borrow_mut();
 
+        if *has_read {
+            return true;
+        }
+
+        *has_read = true;
+        false
+    })
+}
+#[cfg(target_arch = "wasm32")]
+fn try_mark_reading() -> bool {
+    HAS_READ.swap(true, std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Marks the current thread as no longer holding a read guard.
+#[cfg(not(target_arch = "wasm32"))]
+fn clear_reading() {
+    HAS_READ.with(|has_read| *has_read.borrow_mut() = false);
+}
+#[cfg(target_arch = "wasm32")]
+fn clear_reading() {
+    HAS_READ.store(false, std::sync::atomic::Ordering::Relaxed);
+}
+
+impl<W: AppWorld + 'static> AppWorldWrapper<W> {
     /// Acquire read access to AppWorld.
     ///
     /// # Panics
@@ -140,18 +267,36 @@ impl<W: AppWorld + 'static> AppWorldWrapper<W> {
     /// 2. Thread B calls `AppWorld::msg`, which attempts to acquire a write lock
     /// 3. Thread A attempts to acquire a second read guard while the first is still active
     pub fn read(&self) -> WorldReadGuard<'_, W> {
-        Self::HAS_READ.with(|has_read| {
-            let mut has_read = has_read.borrow_mut();
-
-            if *has_read {
-                panic!("Thread already holds read guard")
-            }
-
-            *has_read = true
-        });
+        mark_reading();
         WorldReadGuard {
             guard: self.world.read().unwrap(),
-            read_tracker: &Self::HAS_READ,
+        }
+    }
+
+    /// Like [`read`](Self::read), but instead of blocking, returns a [`WorldError`] if the lock
+    /// can't be acquired without blocking or is poisoned.
+    ///
+    /// Unlike [`read`](Self::read), this doesn't panic if the current thread is already holding
+    /// a read guard; it returns `Err(WorldError::AlreadyReading)` instead, so a
+    /// latency-sensitive caller (e.g. a render loop polling state every frame) can gracefully
+    /// skip work for this frame rather than deadlocking or aborting.
+    ///
+    /// Note for render-loop callers that only need an `Option`: this returns
+    /// `Result<_, WorldError>` rather than `Option<WorldReadGuard>`, reusing the same
+    /// [`WorldError`] that [`try_msg`](Self::try_msg) and [`recover`](Self::recover) already
+    /// established, instead of introducing a second, differently-shaped `try_read`. Use
+    /// [`Result::ok`] if you just want the `Option`.
+    pub fn try_read(&self) -> Result<WorldReadGuard<'_, W>, WorldError> {
+        if try_mark_reading() {
+            return Err(WorldError::AlreadyReading);
+        }
+
+        match lock::try_read(&self.world) {
+            Ok(guard) => Ok(WorldReadGuard { guard }),
+            Err(err) => {
+                clear_reading();
+                Err(err)
+            }
         }
     }
 
@@ -163,9 +308,55 @@ impl<W: AppWorld + 'static> AppWorldWrapper<W> {
     /// This .write() method is useful when writing tests where you want to quickly set up some
     /// initial state.
     #[cfg(feature = "test-utils")]
-    pub fn write(&self) -> std::sync::RwLockWriteGuard<'_, W> {
+    pub fn write(&self) -> RwLockWriteGuard<'_, W> {
         self.world.write().unwrap()
     }
+
+    /// Acquire read access to AppWorld, returning a guard that owns a handle to the world
+    /// instead of borrowing `self`.
+    ///
+    /// This is useful when you need a guard that outlives `&self`, e.g. to store it in a
+    /// `'static` struct or move it into a non-`Send` future (such as one driven by
+    /// `tokio::task::spawn_local`). See [`OwnedWorldReadGuard`] for why this guard can't be sent
+    /// to a different OS thread.
+    ///
+    /// # Panics
+    /// Panics if the current thread is already holding a read guard, for the same reason that
+    /// [`AppWorldWrapper::read`] panics on a same-thread double read.
+    pub fn read_owned(&self) -> OwnedWorldReadGuard<W> {
+        mark_reading();
+
+        let world = self.world.clone();
+        let guard = world.read().unwrap();
+        // SAFETY: `guard` borrows from `world`, an `Arc` clone of the same `RwLock` that's
+        // stored alongside it in `OwnedWorldReadGuard`. That `Arc` keeps the lock alive for as
+        // long as the guard exists, and `guard` is declared before `world` in
+        // `OwnedWorldReadGuard` so it's dropped first.
+        let guard: RwLockReadGuard<'static, W> = unsafe { std::mem::transmute(guard) };
+
+        OwnedWorldReadGuard { guard, world }
+    }
+
+    /// Acquire write access to AppWorld, returning a guard that owns a handle to the world
+    /// instead of borrowing `self`.
+    ///
+    /// This is useful when you need a guard that outlives `&self`, e.g. to store it in a
+    /// `'static` struct or move it into a non-`Send` future (such as one driven by
+    /// `tokio::task::spawn_local`). See [`OwnedWorldWriteGuard`] for why this guard can't be sent
+    /// to a different OS thread.
+    ///
+    /// Under normal circumstances you should only ever write to the world through the `.msg()`
+    /// method. This is useful when writing tests where you want to quickly set up some initial
+    /// state.
+    #[cfg(feature = "test-utils")]
+    pub fn write_owned(&self) -> OwnedWorldWriteGuard<W> {
+        let world = self.world.clone();
+        let guard = world.write().unwrap();
+        // SAFETY: See the safety comment in `read_owned`; the same reasoning applies here.
+        let guard: RwLockWriteGuard<'static, W> = unsafe { std::mem::transmute(guard) };
+
+        OwnedWorldWriteGuard { guard, world }
+    }
 }
 
 impl<W: AppWorld> Clone for AppWorldWrapper<W> {
@@ -179,7 +370,6 @@ impl<W: AppWorld> Clone for AppWorldWrapper<W> {
 /// Holds a read guard on a World.
 pub struct WorldReadGuard<'a, W> {
     guard: RwLockReadGuard<'a, W>,
-    read_tracker: &'static LocalKey<RefCell<bool>>,
 }
 impl<'a, W> Deref for WorldReadGuard<'a, W> {
     type Target = RwLockReadGuard<'a, W>;
@@ -190,13 +380,91 @@ impl<'a, W> Deref for WorldReadGuard<'a, W> {
 }
 impl<'a, W> Drop for WorldReadGuard<'a, W> {
     fn drop(&mut self) {
-        self.read_tracker.with(|has_reads| {
-            *has_reads.borrow_mut() = false;
-        })
+        clear_reading();
+    }
+}
+
+/// Holds a read guard on a World, along with the `Arc` that keeps the World alive.
+///
+/// Unlike [`WorldReadGuard`], this guard doesn't borrow from the [`AppWorldWrapper`] that created
+/// it, so it can be stored in a `'static` struct or moved into a non-`Send` future (such as one
+/// driven by `tokio::task::spawn_local`).
+///
+/// # `Send`
+///
+/// This guard wraps a `std::sync::RwLockReadGuard`, which is `!Send` (on some platforms a lock
+/// must be released by the thread that acquired it), so `OwnedWorldReadGuard` is `!Send` too and
+/// can't be moved into a `thread::spawn`'d thread:
+///
+/// ```compile_fail
+/// # struct MyWorld;
+/// # impl app_world::AppWorld for MyWorld {
+/// #     type Message = ();
+/// #     fn msg(&mut self, _message: Self::Message) {}
+/// # }
+/// let wrapper = app_world::AppWorldWrapper::new(MyWorld);
+/// let guard = wrapper.read_owned();
+/// std::thread::spawn(move || {
+///     let _ = &guard;
+/// });
+/// ```
+pub struct OwnedWorldReadGuard<W: AppWorld + 'static> {
+    guard: RwLockReadGuard<'static, W>,
+    // Kept alive so that the data `guard` borrows from remains valid. Declared after `guard` so
+    // that it's dropped after `guard` is. Never read directly; it's RAII-only.
+    #[allow(dead_code)]
+    world: Arc<RwLock<W>>,
+}
+impl<W: AppWorld> Deref for OwnedWorldReadGuard<W> {
+    type Target = RwLockReadGuard<'static, W>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+impl<W: AppWorld> Drop for OwnedWorldReadGuard<W> {
+    fn drop(&mut self) {
+        clear_reading();
     }
 }
 
-#[cfg(test)]
+/// Holds a write guard on a World, along with the `Arc` that keeps the World alive.
+///
+/// Unlike the guard returned by [`AppWorldWrapper::write`], this guard doesn't borrow from the
+/// `AppWorldWrapper` that created it, so it can be stored in a `'static` struct or moved into a
+/// non-`Send` future (such as one driven by `tokio::task::spawn_local`).
+///
+/// Like [`OwnedWorldReadGuard`], this guard wraps a `std::sync::RwLockWriteGuard`, which is
+/// `!Send`, so it can't be moved into a `thread::spawn`'d thread either.
+#[cfg(feature = "test-utils")]
+pub struct OwnedWorldWriteGuard<W: AppWorld + 'static> {
+    guard: RwLockWriteGuard<'static, W>,
+    // Kept alive so that the data `guard` borrows from remains valid. Declared after `guard` so
+    // that it's dropped after `guard` is. Never read directly; it's RAII-only.
+    #[allow(dead_code)]
+    world: Arc<RwLock<W>>,
+}
+#[cfg(feature = "test-utils")]
+impl<W: AppWorld> Deref for OwnedWorldWriteGuard<W> {
+    type Target = RwLockWriteGuard<'static, W>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+#[cfg(feature = "test-utils")]
+impl<W: AppWorld> std::ops::DerefMut for OwnedWorldWriteGuard<W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+// Under the `shuttle` feature, `lock::{Arc, RwLock, ...}` resolve to shuttle's own primitives,
+// which only work when driven by shuttle's scheduler (`shuttle::check_dfs`/`check_random`); an
+// ordinary `#[test]` calling `AppWorldWrapper::read` directly would panic with "Are you accessing
+// a Shuttle primitive outside of a Shuttle test?". These tests are the non-shuttle counterpart to
+// [`shuttle_tests`] below, so they're disabled whenever that backend is active.
+#[cfg(all(test, not(feature = "shuttle")))]
 mod tests {
     use super::*;
     use std::thread;
@@ -267,7 +535,107 @@ mod tests {
         let _guard = world.read();
     }
 
-    #[derive(Default)]
+    /// Verify that `try_read` reports `WorldError::AlreadyReading` instead of panicking when the
+    /// current thread already holds a read guard.
+    #[test]
+    fn try_read_reports_already_reading_instead_of_panicking() {
+        let world = AppWorldWrapper::new(TestWorld::default());
+
+        let _guard = world.read();
+        assert!(matches!(world.try_read(), Err(WorldError::AlreadyReading)));
+    }
+
+    /// Verify that a panicking `try_msg` handler doesn't poison the lock, and that the world is
+    /// still usable afterward.
+    #[test]
+    fn try_msg_handler_panic_does_not_poison_the_lock() {
+        let world = AppWorldWrapper::new(PanickyWorld::default());
+
+        let result = world.try_msg(PanickyMessage::Panic);
+        assert!(matches!(result, Err(WorldError::HandlerPanicked)));
+
+        // The lock isn't poisoned, so normal reads and writes still work.
+        assert_eq!(world.read().count, 0);
+        world.msg(PanickyMessage::Increment);
+        assert_eq!(world.read().count, 1);
+    }
+
+    /// Verify that a panic inside a plain `msg` call (not caught, unlike `try_msg`) poisons the
+    /// lock, and that `recover` clears the poisoning and hands back a write guard.
+    #[test]
+    fn msg_panic_poisons_the_lock_and_recover_clears_it() {
+        let world = AppWorldWrapper::new(PanickyWorld::default());
+
+        let join = thread::spawn({
+            let world = world.clone();
+            move || world.msg(PanickyMessage::Panic)
+        })
+        .join();
+        assert!(join.is_err());
+
+        assert!(matches!(world.try_read(), Err(WorldError::Poisoned)));
+
+        let mut guard = world.recover();
+        guard.count = 0;
+        drop(guard);
+
+        assert_eq!(world.read().count, 0);
+    }
+
+    /// Verify that `recover` panics if called when the lock isn't actually poisoned.
+    #[test]
+    #[should_panic = "AppWorldWrapper::recover called but the lock wasn't poisoned"]
+    fn recover_panics_if_the_lock_is_not_poisoned() {
+        let world = AppWorldWrapper::new(PanickyWorld::default());
+
+        drop(world.recover());
+    }
+
+    #[derive(Default, Clone)]
+    pub(crate) struct TestWorld {
+        pub(crate) was_mutated: bool,
+    }
+    impl AppWorld for TestWorld {
+        type Message = ();
+        fn msg(&mut self, _message: Self::Message) {
+            self.was_mutated = true;
+        }
+    }
+
+    #[derive(Default, Clone)]
+    struct PanickyWorld {
+        count: u32,
+    }
+    enum PanickyMessage {
+        Increment,
+        Panic,
+    }
+    impl AppWorld for PanickyWorld {
+        type Message = PanickyMessage;
+        fn msg(&mut self, message: Self::Message) {
+            match message {
+                PanickyMessage::Increment => self.count += 1,
+                PanickyMessage::Panic => panic!("PanickyWorld::msg panicked"),
+            }
+        }
+    }
+}
+
+/// Model-checked coverage of the double-read deadlock prevention logic.
+///
+/// Unlike [`tests::deadlock_prevention_same_thread_double_read_another_thread_write`], which
+/// relies on `thread::sleep` to nudge execution into one particular racy interleaving, these
+/// tests run under [shuttle](https://docs.rs/shuttle)'s scheduler, which deterministically
+/// replays many (or, under `check_dfs`, all) possible interleavings of the spawned threads.
+#[cfg(all(test, feature = "shuttle"))]
+mod shuttle_tests {
+    use super::*;
+    use lock::thread;
+
+    // A separate, minimal `AppWorld` rather than reusing `tests::TestWorld`: the non-shuttle
+    // `tests` module is disabled under this feature (see the comment above it), since its assertions
+    // run outside of shuttle's scheduler.
+    #[derive(Default, Clone)]
     struct TestWorld {
         was_mutated: bool,
     }
@@ -277,4 +645,67 @@ mod tests {
             self.was_mutated = true;
         }
     }
+
+    /// Assert that the "thread already holds read guard" panic fires on every schedule where a
+    /// thread attempts a nested read, and that no schedule deadlocks, including the write that
+    /// another thread makes once the first guard has been dropped.
+    #[test]
+    fn nested_read_always_panics_never_deadlocks() {
+        shuttle::check_dfs(
+            || {
+                let world = AppWorldWrapper::new(TestWorld { was_mutated: false });
+                let world_clone = world.clone();
+
+                let handle = thread::spawn(move || {
+                    {
+                        let _guard_1 = world.read();
+
+                        let second_read = std::panic::catch_unwind(std::panic::AssertUnwindSafe(
+                            || world.read(),
+                        ));
+                        assert!(
+                            second_read.is_err(),
+                            "a nested read on the same thread must panic instead of deadlocking"
+                        );
+                    }
+
+                    // `_guard_1` is dropped before spawning the writer, so the writer's attempt
+                    // to acquire the write lock can't deadlock against a read guard this thread
+                    // is still holding.
+                    let writer = thread::spawn(move || {
+                        world_clone.msg(());
+                    });
+                    writer.join().unwrap();
+                });
+
+                handle.join().unwrap();
+            },
+            None,
+        );
+    }
+
+    /// Assert that concurrent reads from different threads never deadlock, across every
+    /// schedule.
+    #[test]
+    fn concurrent_reads_on_different_threads_never_deadlock() {
+        shuttle::check_dfs(
+            || {
+                let world = AppWorldWrapper::new(TestWorld { was_mutated: false });
+
+                let handles: Vec<_> = (0..3)
+                    .map(|_| {
+                        let world = world.clone();
+                        thread::spawn(move || {
+                            let _guard = world.read();
+                        })
+                    })
+                    .collect();
+
+                for handle in handles {
+                    handle.join().unwrap();
+                }
+            },
+            None,
+        );
+    }
 }
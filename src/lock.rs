@@ -0,0 +1,65 @@
+//! Selects which concurrency primitives back [`AppWorldWrapper`](crate::AppWorldWrapper).
+//!
+//! - On `wasm32-unknown-unknown` there's only ever one thread, so an `Arc<RwLock<_>>` is pure
+//!   overhead (atomics that never contend, a lock that never blocks). We use an `Rc<RefCell<_>>`
+//!   stand-in instead, matching the real `std::sync::RwLock` API closely enough that
+//!   `AppWorldWrapper` doesn't need to know which backend it's built on.
+//! - Otherwise we use the real `std` primitives, unless the `shuttle` feature is enabled, in
+//!   which case we use [shuttle](https://docs.rs/shuttle)'s deterministic-scheduling equivalents
+//!   so tests can exhaustively (or randomly) explore thread interleavings instead of relying on
+//!   timing (e.g. `thread::sleep`) to hit a racy schedule.
+//!
+//! Everything here is `pub(crate)`; the choice of backend is an implementation detail that isn't
+//! exposed to downstream users of the crate.
+
+#[cfg(all(target_arch = "wasm32", feature = "shuttle"))]
+compile_error!("the `shuttle` feature is not supported on wasm32");
+
+#[cfg(not(feature = "shuttle"))]
+pub(crate) use std::thread_local;
+
+// Only `shuttle_tests` (gated on `all(test, feature = "shuttle")`) spawns threads through
+// `lock::thread`, so the re-export is gated the same way; an unconditional one (shuttle-only or,
+// worse, always-on) would go unused and trip `-D warnings` on a plain `cargo build --features
+// shuttle`.
+#[cfg(all(test, feature = "shuttle"))]
+pub(crate) use shuttle::thread;
+#[cfg(feature = "shuttle")]
+pub(crate) use shuttle::thread_local;
+
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "shuttle")))]
+pub(crate) use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "shuttle"))]
+pub(crate) use shuttle::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+/// Acquire a read guard without blocking, translating the backend's lock-specific error into a
+/// [`WorldError`](crate::error::WorldError).
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn try_read<W>(
+    lock: &RwLock<W>,
+) -> Result<RwLockReadGuard<'_, W>, crate::error::WorldError> {
+    match lock.try_read() {
+        Ok(guard) => Ok(guard),
+        Err(TryLockError::WouldBlock) => Err(crate::error::WorldError::WouldBlock),
+        Err(TryLockError::Poisoned(_)) => Err(crate::error::WorldError::Poisoned),
+    }
+}
+
+/// Acquire a write guard without blocking, translating the backend's lock-specific error into a
+/// [`WorldError`](crate::error::WorldError).
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn try_write<W>(
+    lock: &RwLock<W>,
+) -> Result<RwLockWriteGuard<'_, W>, crate::error::WorldError> {
+    match lock.try_write() {
+        Ok(guard) => Ok(guard),
+        Err(TryLockError::WouldBlock) => Err(crate::error::WorldError::WouldBlock),
+        Err(TryLockError::Poisoned(_)) => Err(crate::error::WorldError::Poisoned),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod wasm;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use wasm::{try_read, try_write, Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
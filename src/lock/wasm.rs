@@ -0,0 +1,64 @@
+//! A single-threaded stand-in for `Arc<RwLock<_>>`, backed by `Rc<RefCell<_>>`, used on
+//! `wasm32-unknown-unknown`.
+
+use std::cell::{Ref, RefCell, RefMut};
+use std::convert::Infallible;
+use std::rc::Rc;
+
+pub(crate) type Arc<T> = Rc<T>;
+
+/// Mimics the subset of `std::sync::RwLock`'s API that
+/// [`AppWorldWrapper`](crate::AppWorldWrapper) relies on, backed by a `RefCell` instead of an OS
+/// lock.
+///
+/// There's no poisoning on wasm (a panic can't leave another thread holding a lock, since
+/// there's only one thread), so `read`/`write` return a `Result` only to keep call sites
+/// (`.read().unwrap()`) identical to the native backend.
+pub(crate) struct RwLock<W>(RefCell<W>);
+
+impl<W> RwLock<W> {
+    pub(crate) fn new(world: W) -> Self {
+        RwLock(RefCell::new(world))
+    }
+
+    /// # Panics
+    /// Panics if a write guard on this same `RwLock` is currently live, mirroring the
+    /// panic-on-live-borrow semantics of [`RefCell::borrow`].
+    pub(crate) fn read(&self) -> Result<RwLockReadGuard<'_, W>, Infallible> {
+        Ok(self.0.borrow())
+    }
+
+    /// # Panics
+    /// Panics if a read or write guard on this same `RwLock` is currently live, mirroring the
+    /// panic-on-live-borrow semantics of [`RefCell::borrow_mut`].
+    pub(crate) fn write(&self) -> Result<RwLockWriteGuard<'_, W>, Infallible> {
+        Ok(self.0.borrow_mut())
+    }
+
+    fn try_read(&self) -> Result<RwLockReadGuard<'_, W>, ()> {
+        self.0.try_borrow().map_err(|_| ())
+    }
+
+    fn try_write(&self) -> Result<RwLockWriteGuard<'_, W>, ()> {
+        self.0.try_borrow_mut().map_err(|_| ())
+    }
+}
+
+pub(crate) type RwLockReadGuard<'a, W> = Ref<'a, W>;
+pub(crate) type RwLockWriteGuard<'a, W> = RefMut<'a, W>;
+
+/// Acquire a read guard without blocking. There's no poisoning on wasm (a panic can't leave
+/// another thread holding the borrow, since there's only one thread), so the only possible
+/// error is [`WorldError::WouldBlock`](crate::error::WorldError::WouldBlock).
+pub(crate) fn try_read<W>(
+    lock: &RwLock<W>,
+) -> Result<RwLockReadGuard<'_, W>, crate::error::WorldError> {
+    lock.try_read().map_err(|_| crate::error::WorldError::WouldBlock)
+}
+
+/// Acquire a write guard without blocking. See [`try_read`] for why poisoning can't occur here.
+pub(crate) fn try_write<W>(
+    lock: &RwLock<W>,
+) -> Result<RwLockWriteGuard<'_, W>, crate::error::WorldError> {
+    lock.try_write().map_err(|_| crate::error::WorldError::WouldBlock)
+}